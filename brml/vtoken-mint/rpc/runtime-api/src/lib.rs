@@ -0,0 +1,38 @@
+// Copyright 2019-2020 Liebi Technologies.
+// This file is part of Bifrost.
+
+// Bifrost is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Bifrost is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Bifrost.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API for the Mint pallet, letting a node query a minter's currently claimable
+//! BNC reward without submitting a transaction.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// The runtime API exposed by the Mint pallet for querying a minter's projected reward.
+	pub trait MintRuntimeApi<AccountId, AssetId, Balance> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// The BNC reward `account` could claim right now under the settlement model.
+		fn get_bnc_reward(account: AccountId) -> Balance;
+
+		/// The BNC reward `account` could claim right now for `asset_id` under the
+		/// currency-weight model.
+		fn get_vtoken_bnc_reward(asset_id: AssetId, account: AccountId) -> Balance;
+	}
+}
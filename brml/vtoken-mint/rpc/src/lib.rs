@@ -0,0 +1,113 @@
+// Copyright 2019-2020 Liebi Technologies.
+// This file is part of Bifrost.
+
+// Bifrost is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Bifrost is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Bifrost.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC wrapper around [`bifrost_mint_rpc_runtime_api::MintRuntimeApi`], mirroring the
+//! `jsonrpc-core`/`jsonrpc-derive` pattern used for Bifrost's vtoken exchange-rate RPC.
+//!
+//! Deliberately built on `jsonrpc-core`/`jsonrpc-derive` rather than `jsonrpsee`: this tree
+//! is pinned to sp-api/codec 2.0.0-era Substrate, whose node RPCs are all wired up through
+//! `jsonrpc-core`, and no `jsonrpsee` version from that period exposes the `#[rpc(client,
+//! server)]`/`#[method(...)]` macro API. No node in this slice calls
+//! `impl_runtime_apis!` for `MintRuntimeApi` yet, so this RPC is not reachable until a
+//! node crate does so and registers [`Mint`] with its `jsonrpc-core` IO handler.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_rpc::number::NumberOrHex;
+
+pub use bifrost_mint_rpc_runtime_api::MintRuntimeApi;
+
+/// The RPC surface exposed to light clients/wallets for reading projected Mint rewards.
+#[rpc]
+pub trait MintRpcApi<BlockHash, AccountId, AssetId> {
+	/// The BNC reward `account` could claim right now under the settlement model.
+	#[rpc(name = "mint_getBncReward")]
+	fn get_bnc_reward(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// The BNC reward `account` could claim right now for `asset_id` under the
+	/// currency-weight model.
+	#[rpc(name = "mint_getVtokenBncReward")]
+	fn get_vtoken_bnc_reward(
+		&self,
+		asset_id: AssetId,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+}
+
+/// A struct that implements [`MintRpcApi`].
+pub struct Mint<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Mint<C, Block> {
+	/// Create a new `Mint` RPC handler backed by `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, AssetId, Balance>
+	MintRpcApi<<Block as BlockT>::Hash, AccountId, AssetId> for Mint<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: MintRuntimeApi<Block, AccountId, AssetId, Balance>,
+	AccountId: Codec,
+	AssetId: Codec,
+	Balance: Codec + Into<u128>,
+{
+	fn get_bnc_reward(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reward = api.get_bnc_reward(&at, account).map_err(runtime_error_into_rpc_err)?;
+		Ok(NumberOrHex::Hex(reward.into().into()))
+	}
+
+	fn get_vtoken_bnc_reward(
+		&self,
+		asset_id: AssetId,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reward = api.get_vtoken_bnc_reward(&at, asset_id, account)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(NumberOrHex::Hex(reward.into().into()))
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -16,19 +16,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use fixed::{types::extra::U0, FixedU128};
-type Fix = FixedU128<U0>;
-
 use frame_support::{
-	Parameter, ensure, decl_module, decl_error, decl_storage, debug,
-	traits::{Currency, ReservableCurrency, Get},
+	Parameter, ensure, decl_module, decl_error, decl_event, decl_storage, debug,
+	traits::{EnsureOrigin, Get},
 };
 
-use frame_system;
+use frame_system::{self, ensure_signed};
 use node_primitives::MintTrait;
+use orml_traits::{DataProvider, MultiCurrency};
 use sp_runtime::traits::{
 	AtLeast32Bit, Member, Saturating,
-	MaybeSerializeDeserialize, Zero, UniqueSaturatedInto
+	MaybeSerializeDeserialize, Zero, UniqueSaturatedInto, UniqueSaturatedFrom
 };
 
 mod mock;
@@ -36,9 +34,15 @@ mod tests;
 
 /// The balance type of this module.
 pub type BalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Fixed-point precision `AccBncPerPoint` (and its per-asset counterparts) are scaled by,
+/// so that `point * AccBncPerPoint / PRECISION` stays accurate despite integer division.
+const PRECISION: u128 = 1_000_000_000_000;
 
 pub trait Config: frame_system::Config {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 	/// The arithmetic type of asset identifier.
 	type AssetId: Member
 		+ Parameter
@@ -46,8 +50,9 @@ pub trait Config: frame_system::Config {
 		+ Default
 		+ Copy
 		+ MaybeSerializeDeserialize;
-	/// The currency trait.
-	type Currency: ReservableCurrency<Self::AccountId>;
+	/// the multi-asset backend rewards (BNC and any other registered token) are paid out
+	/// through, rather than a single dedicated `Currency`
+	type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId>;
 	/// bnc price half interval amounts
 	type PriceHalfBlockInterval: Get<u32>;
 	/// bnc issue max block number
@@ -56,24 +61,76 @@ pub trait Config: frame_system::Config {
 	type MaxTxAmount: Get<u32>;
 	/// bnc pledge base amounts
 	type PledgeBaseAmount: Get<u32>;
+	/// the longest a vote-escrowed pledge may be locked for; a pledge's weight decays
+	/// linearly to zero as its unlock block approaches
+	type MaxLockDuration: Get<u32>;
+	/// oracle-style feed for the BNC price; when it yields a fresh value it drives
+	/// `BncPrice` directly, falling back to the halving schedule only once it goes stale
+	type PriceProvider: DataProvider<Self::AssetId, BalanceOf<Self>>;
+	/// the asset id BNC is registered under, used as the `PriceProvider` lookup key and as
+	/// the `MultiCurrency` id rewards are deposited in
+	type BncCurrencyId: Get<Self::AssetId>;
+	/// blocks since the last oracle update after which the feed is considered stale and
+	/// the halving schedule resumes
+	type PriceStalenessThreshold: Get<u32>;
+	/// origin allowed to administer this pallet's governance-controlled parameters
+	type ControlOrigin: EnsureOrigin<Self::Origin>;
 }
 
 decl_storage! {
 	trait Store for Module<T: Config> as Mint {
-		/// bnc total stimulate amount
+		/// bnc total stimulate amount not yet folded into an accumulator
 		BncSum: BalanceOf<T>;
 		/// record block_number and price for caculate bnc_price
 		BncPrice get(fn number_price) config(): (T::BlockNumber, BalanceOf<T>);
 		/// record block_number and price for issue bnc reward
 		BncMonitor: ((T::BlockNumber, BalanceOf<T>), BalanceOf<T>, u32);
-		/// bnc mint (apply to settlement model)
-		BncMint get(fn bnc_mint): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+		/// last block at which `PriceProvider` supplied a genuinely new BNC price (i.e. one
+		/// that differed from `LastOracleValue`), as opposed to re-reporting the same reading,
+		/// or at which `set_bnc_price` last overrode the price
+		LastOracleUpdate get(fn last_oracle_update): T::BlockNumber;
+		/// the last value read back from `PriceProvider`, used to tell a genuinely fresh
+		/// reading apart from the provider simply repeating its last cached value
+		LastOracleValue get(fn last_oracle_value): Option<BalanceOf<T>>;
+
+		/// accumulated bnc reward per point, scaled by `PRECISION` (settlement model)
+		AccBncPerPoint get(fn acc_bnc_per_point): BalanceOf<T>;
+		/// total point across all minters (settlement model)
+		TotalPoint get(fn total_point): BalanceOf<T>;
+		/// bnc mint, (point, reward_debt) (apply to settlement model)
+		BncMint get(fn bnc_mint): map hasher(blake2_128_concat) T::AccountId
+			=> (BalanceOf<T>, BalanceOf<T>);
+		/// bnc reward a minter has accrued but not yet claimed (settlement model)
+		Claimable get(fn claimable): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
 		/// asset weight (apply to currency weight model)
 		VtokenWeightScore get(fn vtoken_weight): map hasher(blake2_128_concat) T::AssetId
 			=> (BalanceOf<T>, BalanceOf<T>);
-		/// bnc mint by weight (apply to currency weight model)
+		/// accumulated bnc reward per point, scaled by `PRECISION`, per asset (currency weight model)
+		AccBncPerPointByAsset get(fn acc_bnc_per_point_by_asset): map hasher(blake2_128_concat) T::AssetId
+			=> BalanceOf<T>;
+		/// total point per asset (currency weight model)
+		TotalPointByAsset get(fn total_point_by_asset): map hasher(blake2_128_concat) T::AssetId
+			=> BalanceOf<T>;
+		/// bnc mint by weight, (point, reward_debt) (apply to currency weight model)
 		VtokenWeightMint get(fn vtoken_mint): double_map hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId => (BalanceOf<T>, BalanceOf<T>);
+		/// bnc reward a minter has accrued but not yet claimed, per asset (currency weight model)
+		ClaimableByAsset get(fn claimable_by_asset): double_map hasher(blake2_128_concat) T::AssetId,
 			hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+		/// the vote-escrow lock backing an asset's weighted score: (locked amount, unlock block)
+		VTokenLock get(fn v_token_lock): map hasher(blake2_128_concat) T::AssetId
+			=> (BalanceOf<T>, T::BlockNumber);
+		/// the asset's current ve-weight, decaying linearly to zero as locks mature
+		TotalBias get(fn total_bias): map hasher(blake2_128_concat) T::AssetId => BalanceOf<T>;
+		/// the asset's current decay rate, i.e. how much `TotalBias` loses per block
+		TotalSlope get(fn total_slope): map hasher(blake2_128_concat) T::AssetId => BalanceOf<T>;
+		/// the slope decrement scheduled to apply to an asset once its lock matures
+		SlopeChanges get(fn slope_changes): double_map hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) T::BlockNumber => BalanceOf<T>;
+		/// last block at which an asset's `TotalBias`/`TotalSlope` were checkpointed
+		LastCheckpoint get(fn last_checkpoint): map hasher(blake2_128_concat) T::AssetId => T::BlockNumber;
 	}
 
 	add_extra_genesis {
@@ -84,18 +141,199 @@ decl_storage! {
 
 }
 
+decl_event! {
+	pub enum Event<T> where
+		Balance = BalanceOf<T>,
+		AssetId = <T as Config>::AssetId,
+	{
+		/// Governance reset `BncPrice` to a new value. \[new_price\]
+		BncPriceSet(Balance),
+		/// Governance forced an out-of-schedule issuance.
+		BncIssueForced,
+		/// Governance registered an asset's base vtoken weight score. \[asset_id, score\]
+		VtokenScoreInitialized(AssetId, Balance),
+		/// Governance cleared `BncMonitor` back to its zero state.
+		MonitorCleared,
+	}
+}
+
 decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Reset `BncPrice` to `new_price`, e.g. to correct a stale or mispriced feed.
+		#[weight = T::DbWeight::get().writes(1)]
+		fn set_bnc_price(origin, new_price: BalanceOf<T>) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let current_block = frame_system::Module::<T>::block_number();
+			BncPrice::<T>::put((current_block, new_price));
+
+			// Absorb whatever the oracle is currently reporting so that `on_finalize`
+			// does not mistake its unchanged reading for a fresh tick on the very next
+			// block and immediately clobber this override.
+			if let Some(price) = T::PriceProvider::get(&T::BncCurrencyId::get()) {
+				LastOracleValue::<T>::put(price);
+			}
+			LastOracleUpdate::<T>::put(current_block);
+
+			Self::deposit_event(RawEvent::BncPriceSet(new_price));
+		}
+
+		/// Trigger a currency-weight-model issuance immediately, without waiting for
+		/// `on_finalize`'s usual schedule.
+		#[weight = T::DbWeight::get().reads_writes(4, 4)]
+		fn force_issue(origin) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Self::issue_bnc_by_weight()?;
+			Self::deposit_event(RawEvent::BncIssueForced);
+		}
+
+		/// Register `asset_id`'s base vtoken weight score.
+		#[weight = T::DbWeight::get().writes(1)]
+		fn init_vtoken_score(origin, asset_id: T::AssetId, score: BalanceOf<T>) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Self::init_v_token_score(asset_id, score);
+			Self::deposit_event(RawEvent::VtokenScoreInitialized(asset_id, score));
+		}
+
+		/// Emergency reset of `BncMonitor` back to its zero state.
+		#[weight = T::DbWeight::get().writes(1)]
+		fn force_clear_monitor(origin) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let zero_balance: BalanceOf<T> = Zero::zero();
+			let zero_block_number: T::BlockNumber = Zero::zero();
+			BncMonitor::<T>::put(((zero_block_number, zero_balance), zero_balance, 0u32));
+			Self::deposit_event(RawEvent::MonitorCleared);
+		}
+
+		/// Pay out the caller's accrued settlement-model reward and reset their reward debt.
+		#[weight = T::DbWeight::get().reads_writes(2, 2)]
+		fn claim(origin) {
+			let who = ensure_signed(origin)?;
+
+			let acc = AccBncPerPoint::<T>::get();
+			let (point, reward_debt) = BncMint::<T>::get(&who);
+			let settled = Self::pending_reward(point, reward_debt, acc);
+			let reward = Claimable::<T>::take(&who).saturating_add(settled);
+			ensure!(reward.ne(&Zero::zero()), Error::<T>::BncAmountNotExist);
+
+			T::MultiCurrency::deposit(T::BncCurrencyId::get(), &who, reward)
+				.map_err(|_| Error::<T>::DepositBncFailure)?;
+			BncMint::<T>::mutate(&who, |(point, reward_debt)| {
+				*reward_debt = Self::accrued_reward(*point, acc);
+			});
+		}
+
+		/// Pay out the caller's accrued currency-weight-model reward for `asset_id`.
+		#[weight = T::DbWeight::get().reads_writes(2, 2)]
+		fn claim_by_weight(origin, asset_id: T::AssetId) {
+			let who = ensure_signed(origin)?;
+
+			let acc = AccBncPerPointByAsset::<T>::get(&asset_id);
+			let (point, reward_debt) = VtokenWeightMint::<T>::get(&asset_id, &who);
+			let settled = Self::pending_reward(point, reward_debt, acc);
+			let reward = ClaimableByAsset::<T>::take(&asset_id, &who).saturating_add(settled);
+			ensure!(reward.ne(&Zero::zero()), Error::<T>::BncAmountNotExist);
+
+			T::MultiCurrency::deposit(T::BncCurrencyId::get(), &who, reward)
+				.map_err(|_| Error::<T>::DepositBncFailure)?;
+			VtokenWeightMint::<T>::mutate(&asset_id, &who, |(point, reward_debt)| {
+				*reward_debt = Self::accrued_reward(*point, acc);
+			});
+		}
+
+		/// Extend an asset's vote-escrow lock to a later `new_unlock_block`, boosting its
+		/// remaining weight without adding to the locked amount.
+		///
+		/// `VTokenLock` is keyed by `asset_id` alone, with no owner or token reservation
+		/// backing it, so this is a protocol-level parameter rather than something an
+		/// arbitrary signed account should be able to move for free; restrict it the same
+		/// way as this pallet's other governance-controlled parameters.
+		#[weight = T::DbWeight::get().reads_writes(3, 4)]
+		fn extend_lock(origin, asset_id: T::AssetId, new_unlock_block: T::BlockNumber) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let current_block = frame_system::Module::<T>::block_number();
+			let (amount, unlock_block) = VTokenLock::<T>::get(&asset_id);
+			ensure!(amount.ne(&Zero::zero()), Error::<T>::LockNotExist);
+			ensure!(new_unlock_block.gt(&unlock_block), Error::<T>::LockDurationTooShort);
+
+			Self::reschedule_lock(asset_id, amount, unlock_block, amount, new_unlock_block, current_block);
+		}
+
+		/// Add `additional_amount` to an asset's existing vote-escrow lock, keeping its
+		/// unlock block unchanged.
+		///
+		/// Gated the same way as `extend_lock`: `VTokenLock` has no owner or token
+		/// reservation behind it, so this must stay governance-only rather than open to
+		/// any signed account.
+		#[weight = T::DbWeight::get().reads_writes(3, 4)]
+		fn increase_amount(origin, asset_id: T::AssetId, additional_amount: BalanceOf<T>) {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let current_block = frame_system::Module::<T>::block_number();
+			let (amount, unlock_block) = VTokenLock::<T>::get(&asset_id);
+			ensure!(amount.ne(&Zero::zero()), Error::<T>::LockNotExist);
+
+			let new_amount = amount.saturating_add(additional_amount);
+			Self::reschedule_lock(asset_id, amount, unlock_block, new_amount, unlock_block, current_block);
+		}
 
 		fn on_finalize(current_block_number: T::BlockNumber) {
+			// Advance every locked asset's ve-checkpoint by the single block that just
+			// elapsed; bounded by the number of assets with an active lock, not by the
+			// number of individual pledges.
+			for (asset_id, _) in VTokenLock::<T>::iter() {
+				Self::checkpoint_ve(asset_id, current_block_number);
+			}
+
 			// Get current block generates bnc stimulate
 			let (record_block_number, mut current_bnc_price) = BncPrice::<T>::get();
 			let zero_balance: BalanceOf<T> = Zero::zero();
 			// Check bnc price
 			if current_bnc_price.eq(&zero_balance) { return }
 
-			if current_block_number.saturating_sub(record_block_number)
-				.eq(&T::BlockNumber::from(T::PriceHalfBlockInterval::get())) {
+			// A provider that simply re-reports its last cached value when it has nothing
+			// new is indistinguishable from a live feed unless we compare against what we
+			// last saw, so only treat a reading as fresh when it actually changed.
+			let oracle_reading = T::PriceProvider::get(&T::BncCurrencyId::get());
+			let oracle_ticked = oracle_reading
+				.map_or(false, |price| LastOracleValue::<T>::get().ne(&Some(price)));
+			if let Some(price) = oracle_reading {
+				if oracle_ticked {
+					LastOracleValue::<T>::put(price);
+					LastOracleUpdate::<T>::put(current_block_number);
+				}
+			}
+
+			if oracle_ticked {
+				// A genuinely new oracle reading always takes priority over both the
+				// halving schedule and any manually governance-set price.
+				let price = oracle_reading.expect("oracle_ticked implies oracle_reading is Some; qed");
+				BncPrice::<T>::mutate(|(record_block_number, bnc_price)| {
+					*record_block_number = current_block_number;
+					*bnc_price = price;
+				});
+				current_bnc_price = price;
+			} else if current_block_number.saturating_sub(LastOracleUpdate::<T>::get())
+				.gt(&T::BlockNumber::from(T::PriceStalenessThreshold::get()))
+				// An oracle tick resets `record_block_number` and `LastOracleUpdate` to the
+				// same block, so an exact `eq` here is only ever reachable on the single
+				// block where the half-interval elapses before staleness does; with
+				// `PriceStalenessThreshold >= PriceHalfBlockInterval` that block never comes
+				// and halving could never fire again. `ge` keeps the halving schedule live
+				// once the interval has elapsed, regardless of how the two are configured.
+				&& current_block_number.saturating_sub(record_block_number)
+					.ge(&T::BlockNumber::from(T::PriceHalfBlockInterval::get()))
+			{
+				// The oracle feed has gone stale (no genuinely new reading within the
+				// staleness threshold); fall back to the mechanical halving.
 				BncPrice::<T>::mutate (|(record_block_number, bnc_price)| {
 					*record_block_number = current_block_number;
 					*bnc_price /= BalanceOf::<T>::from(2u32);
@@ -108,7 +346,7 @@ decl_module! {
 			// Obtain monitor data
 			let ((previous_block_numer, bnc_mint_amount), max_bnc_mint_amount, tx_amount)
 				= BncMonitor::<T>::get();
-			
+
 			// Check issue condition
 			if current_block_number.saturating_sub(previous_block_numer)
 				.eq(&T::BlockNumber::from(T::MaxIssueBlockInterval::get()))
@@ -146,6 +384,159 @@ decl_error! {
 		PledgeAmountNotEnough,
 		/// Bnc issue fail
 		DepositBncFailure,
+		/// No vote-escrow lock exists for this asset
+		LockNotExist,
+		/// The lock has not reached its unlock block yet
+		LockNotExpired,
+		/// The requested unlock block does not extend the current lock
+		LockDurationTooShort,
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Split `amount` roughly in half, handing any odd remainder to the second share.
+	fn split_in_half(amount: BalanceOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
+		let half = amount / BalanceOf::<T>::from(2u32);
+		let remainder = amount.saturating_sub(half);
+		(half, remainder)
+	}
+
+	/// Spread a freshly generated reward of `generate_amount` over `total_point`, scaled by
+	/// `PRECISION`, yielding the increment to add to an `AccBncPerPoint`-style accumulator.
+	fn scaled_increment(generate_amount: BalanceOf<T>, total_point: BalanceOf<T>) -> BalanceOf<T> {
+		let generate_amount: u128 = generate_amount.unique_saturated_into();
+		let total_point: u128 = total_point.unique_saturated_into();
+		let increment = generate_amount.saturating_mul(PRECISION) / total_point;
+		BalanceOf::<T>::unique_saturated_from(increment)
+	}
+
+	/// `point * acc_per_point / PRECISION`, i.e. the cumulative reward `point` has accrued
+	/// under the given accumulator.
+	fn accrued_reward(point: BalanceOf<T>, acc_per_point: BalanceOf<T>) -> BalanceOf<T> {
+		let point: u128 = point.unique_saturated_into();
+		let acc_per_point: u128 = acc_per_point.unique_saturated_into();
+		let accrued = point.saturating_mul(acc_per_point) / PRECISION;
+		BalanceOf::<T>::unique_saturated_from(accrued)
+	}
+
+	/// Reward accrued since `reward_debt` was last set, for `point` under `acc_per_point`.
+	fn pending_reward(point: BalanceOf<T>, reward_debt: BalanceOf<T>, acc_per_point: BalanceOf<T>)
+		-> BalanceOf<T>
+	{
+		Self::accrued_reward(point, acc_per_point).saturating_sub(reward_debt)
+	}
+
+	/// The BNC reward `account` could claim right now under the settlement model, without
+	/// mutating any storage. Backs the `bifrost-mint-rpc-runtime-api` runtime API.
+	pub fn get_bnc_reward(account: T::AccountId) -> BalanceOf<T> {
+		let acc = AccBncPerPoint::<T>::get();
+		let (point, reward_debt) = BncMint::<T>::get(&account);
+		let pending = Self::pending_reward(point, reward_debt, acc);
+		Claimable::<T>::get(&account).saturating_add(pending)
+	}
+
+	/// The BNC reward `account` could claim right now for `asset_id` under the
+	/// currency-weight model, without mutating any storage. Backs the
+	/// `bifrost-mint-rpc-runtime-api` runtime API.
+	pub fn get_vtoken_bnc_reward(asset_id: T::AssetId, account: T::AccountId) -> BalanceOf<T> {
+		let acc = AccBncPerPointByAsset::<T>::get(&asset_id);
+		let (point, reward_debt) = VtokenWeightMint::<T>::get(&asset_id, &account);
+		let pending = Self::pending_reward(point, reward_debt, acc);
+		ClaimableByAsset::<T>::get(&asset_id, &account).saturating_add(pending)
+	}
+
+	/// The per-block decay rate of a lock of `amount`, i.e. `amount / MaxLockDuration`.
+	fn slope_of(amount: BalanceOf<T>) -> BalanceOf<T> {
+		let max_duration = BalanceOf::<T>::from(T::MaxLockDuration::get());
+		if max_duration.eq(&Zero::zero()) {
+			return Zero::zero();
+		}
+		amount / max_duration
+	}
+
+	/// `amount * (unlock_block - current_block) / MaxLockDuration`, clamped to zero once
+	/// the lock has matured.
+	fn bias_of(amount: BalanceOf<T>, unlock_block: T::BlockNumber, current_block: T::BlockNumber)
+		-> BalanceOf<T>
+	{
+		if unlock_block.le(&current_block) {
+			return Zero::zero();
+		}
+		let remaining: u128 = unlock_block.saturating_sub(current_block).unique_saturated_into();
+		let amount: u128 = amount.unique_saturated_into();
+		let max_duration = (T::MaxLockDuration::get() as u128).max(1);
+		BalanceOf::<T>::unique_saturated_from(amount.saturating_mul(remaining) / max_duration)
+	}
+
+	/// Bring an asset's `TotalBias`/`TotalSlope` up to date with `current_block`, applying
+	/// the slope change scheduled at `current_block` itself, then sync `VtokenWeightScore`.
+	/// Slope changes scheduled at blocks strictly between the last checkpoint and
+	/// `current_block` are not applied.
+	fn checkpoint_ve(asset_id: T::AssetId, current_block: T::BlockNumber) {
+		let last = LastCheckpoint::<T>::get(&asset_id);
+		if current_block.le(&last) {
+			return;
+		}
+		let elapsed: u128 = current_block.saturating_sub(last).unique_saturated_into();
+
+		let slope: u128 = TotalSlope::<T>::get(&asset_id).unique_saturated_into();
+		let decay = BalanceOf::<T>::unique_saturated_from(slope.saturating_mul(elapsed));
+		TotalBias::<T>::mutate(&asset_id, |bias| *bias = bias.saturating_sub(decay));
+
+		let matured = SlopeChanges::<T>::get(&asset_id, &current_block);
+		if matured.ne(&Zero::zero()) {
+			TotalSlope::<T>::mutate(&asset_id, |s| *s = s.saturating_sub(matured));
+			SlopeChanges::<T>::remove(&asset_id, &current_block);
+
+			// `slope = amount / MaxLockDuration` truncates, so decaying by slope for
+			// `MaxLockDuration` blocks leaves a residual of `amount % MaxLockDuration`
+			// stuck in `TotalBias`. There is exactly one lock per asset, so once its
+			// slope change has matured its bias is definitely expired; clamp it away.
+			let zero_balance: BalanceOf<T> = Zero::zero();
+			TotalBias::<T>::insert(&asset_id, zero_balance);
+		}
+
+		LastCheckpoint::<T>::insert(&asset_id, current_block);
+		Self::sync_ve_score(asset_id);
+	}
+
+	/// Replace an asset's lock of `old_amount`/`old_unlock` with one of `new_amount`/
+	/// `new_unlock`, keeping `TotalBias`/`TotalSlope`/`SlopeChanges` consistent.
+	fn reschedule_lock(
+		asset_id: T::AssetId,
+		old_amount: BalanceOf<T>,
+		old_unlock: T::BlockNumber,
+		new_amount: BalanceOf<T>,
+		new_unlock: T::BlockNumber,
+		current_block: T::BlockNumber,
+	) {
+		Self::checkpoint_ve(asset_id, current_block);
+
+		let old_slope = Self::slope_of(old_amount);
+		if old_slope.ne(&Zero::zero()) {
+			TotalSlope::<T>::mutate(&asset_id, |s| *s = s.saturating_sub(old_slope));
+			SlopeChanges::<T>::mutate(&asset_id, &old_unlock, |s| *s = s.saturating_sub(old_slope));
+		}
+		let old_bias = Self::bias_of(old_amount, old_unlock, current_block);
+		TotalBias::<T>::mutate(&asset_id, |b| *b = b.saturating_sub(old_bias));
+
+		let new_slope = Self::slope_of(new_amount);
+		if new_slope.ne(&Zero::zero()) {
+			TotalSlope::<T>::mutate(&asset_id, |s| *s = s.saturating_add(new_slope));
+			SlopeChanges::<T>::mutate(&asset_id, &new_unlock, |s| *s = s.saturating_add(new_slope));
+		}
+		let new_bias = Self::bias_of(new_amount, new_unlock, current_block);
+		TotalBias::<T>::mutate(&asset_id, |b| *b = b.saturating_add(new_bias));
+
+		VTokenLock::<T>::insert(&asset_id, (new_amount, new_unlock));
+		LastCheckpoint::<T>::insert(&asset_id, current_block);
+		Self::sync_ve_score(asset_id);
+	}
+
+	/// Mirror an asset's current `TotalBias` into `VtokenWeightScore`'s adjustable component.
+	fn sync_ve_score(asset_id: T::AssetId) {
+		let bias = TotalBias::<T>::get(&asset_id);
+		VtokenWeightScore::<T>::mutate(asset_id, |(_, adjust_score)| *adjust_score = bias);
 	}
 }
 
@@ -154,22 +545,60 @@ impl<T: Config> MintTrait<T::AccountId, BalanceOf<T>, T::AssetId> for Module<T>
 
 	// Statistics bnc
 	fn count_bnc(generate_amount: BalanceOf<T>) {
-		BncSum::<T>::mutate(|bnc_amount| {
-			*bnc_amount = bnc_amount.saturating_add(generate_amount);
-		});
+		// The settlement model is fed directly through `AccBncPerPoint`, while the
+		// currency-weight model is fed through `BncSum` (drained later by
+		// `issue_bnc_by_weight`). Split `generate_amount` between whichever of the two
+		// models is actually active so the same stimulate isn't issued to both in full.
+		//
+		// "Active" for the weight model means some asset actually has minters accruing
+		// `TotalPointByAsset`, not merely a registered `VtokenWeightScore` — an asset
+		// with a score but no points receives no share of `BncSum` in
+		// `issue_bnc_by_weight`, so routing funds there when every asset is pointless
+		// would just have `issue_bnc_by_weight` reset `BncSum` to zero and lose them.
+		let total_point = TotalPoint::<T>::get();
+		let settlement_active = total_point.ne(&Zero::zero());
+		let weight_active = TotalPointByAsset::<T>::iter().any(|(_, point)| point.ne(&Zero::zero()));
+
+		let (settlement_share, weight_share) = match (settlement_active, weight_active) {
+			(true, true) => Self::split_in_half(generate_amount),
+			(true, false) => (generate_amount, Zero::zero()),
+			(false, true) => (Zero::zero(), generate_amount),
+			(false, false) => (Zero::zero(), Zero::zero()),
+		};
+
+		if settlement_share.ne(&Zero::zero()) {
+			let increment = Self::scaled_increment(settlement_share, total_point);
+			AccBncPerPoint::<T>::mutate(|acc| {
+				*acc = acc.saturating_add(increment);
+			});
+		}
+
+		if weight_share.ne(&Zero::zero()) {
+			BncSum::<T>::mutate(|bnc_amount| {
+				*bnc_amount = bnc_amount.saturating_add(weight_share);
+			});
+		}
 	}
 
 	// Settlement model mint
 	fn mint_bnc(minter: T::AccountId, mint_amount: BalanceOf<T>) -> Result<(), Self::Error> {
-		// Judge
-		if BncMint::<T>::contains_key(&minter) {
-			BncMint::<T>::mutate(minter, |v| {
-				*v = v.saturating_add(mint_amount)
+		let acc = AccBncPerPoint::<T>::get();
+		let (point, reward_debt) = BncMint::<T>::get(&minter);
+
+		// Bank whatever the minter's existing point has accrued so far before it moves.
+		let pending = Self::pending_reward(point, reward_debt, acc);
+		if pending.ne(&Zero::zero()) {
+			Claimable::<T>::mutate(&minter, |claimable| {
+				*claimable = claimable.saturating_add(pending);
 			});
-		} else {
-			BncMint::<T>::insert(minter, mint_amount);
 		}
 
+		let new_point = point.saturating_add(mint_amount);
+		BncMint::<T>::insert(&minter, (new_point, Self::accrued_reward(new_point, acc)));
+		TotalPoint::<T>::mutate(|total| {
+			*total = total.saturating_add(mint_amount);
+		});
+
 		let (_, max_bnc_amount, _) = BncMonitor::<T>::get();
 		if mint_amount.gt(&max_bnc_amount) {
 			// Update max_bnc_amount
@@ -184,36 +613,10 @@ impl<T: Config> MintTrait<T::AccountId, BalanceOf<T>, T::AssetId> for Module<T>
 		Ok(())
 	}
 
-	// Settlement model mint
+	// Settlement model mint reward now accrues lazily in `mint_bnc` via `AccBncPerPoint` and
+	// is paid out on demand through the `claim` dispatchable, so there is no longer an O(n)
+	// payout loop to run here. Kept as a no-op to satisfy `MintTrait`.
 	fn issue_bnc() -> Result<(), Self::Error> {
-		// Check Bnc total amount
-		let zero_balance: BalanceOf<T> = Zero::zero();
-		let zero_block_number:  T::BlockNumber= Zero::zero();
-		ensure!(BncSum::<T>::get().ne(&zero_balance), Error::<T>::BncAmountNotExist);
-		let bnc_amount = BncSum::<T>::get();
-		// Get total point
-		let sum: BalanceOf<T> =
-			BncMint::<T>::iter().fold(zero_balance, |acc, x| acc.saturating_add(x.1));
-		// Check minter point
-		ensure!(sum.ne(&zero_balance), Error::<T>::MinterNotExist);
-
-		// Traverse dispatch BNC reward
-		for (minter, point) in BncMint::<T>::iter() {
-			let minter_reward = point.saturating_mul(bnc_amount) / sum;
-			if minter_reward.ne(&zero_balance) {
-				ensure!(
-					T::Currency::deposit_into_existing(&minter, minter_reward).is_ok(),
-					Error::<T>::DepositBncFailure
-				);
-			}
-		}
-		// Reset BncSum
-		BncSum::<T>::put(zero_balance);
-		// Clear BncMint
-		for _ in BncMint::<T>::drain() {};
-		// Clear Monitor data
-		BncMonitor::<T>::put(((zero_block_number, zero_balance), zero_balance, 0u32));
-
 		Ok(())
 	}
 
@@ -231,15 +634,24 @@ impl<T: Config> MintTrait<T::AccountId, BalanceOf<T>, T::AssetId> for Module<T>
 		-> Result<(), Self::Error>
 	{
 		ensure!(Self::v_token_score_exists(asset_id), Error::<T>::AssetScoreNotExist);
-		// Judge
-		if VtokenWeightMint::<T>::contains_key(&asset_id, &minter) {
-			VtokenWeightMint::<T>::mutate(&asset_id, &minter, |v| {
-				*v = v.saturating_add(mint_amount);
+
+		let acc = AccBncPerPointByAsset::<T>::get(&asset_id);
+		let (point, reward_debt) = VtokenWeightMint::<T>::get(&asset_id, &minter);
+
+		// Bank whatever the minter's existing point has accrued so far before it moves.
+		let pending = Self::pending_reward(point, reward_debt, acc);
+		if pending.ne(&Zero::zero()) {
+			ClaimableByAsset::<T>::mutate(&asset_id, &minter, |claimable| {
+				*claimable = claimable.saturating_add(pending);
 			});
-		} else {
-			VtokenWeightMint::<T>::insert(asset_id, minter, mint_amount);
 		}
 
+		let new_point = point.saturating_add(mint_amount);
+		VtokenWeightMint::<T>::insert(&asset_id, &minter, (new_point, Self::accrued_reward(new_point, acc)));
+		TotalPointByAsset::<T>::mutate(&asset_id, |total| {
+			*total = total.saturating_add(mint_amount);
+		});
+
 		// Obtain max_bnc_amount
 		let (_, max_bnc_amount, _) = BncMonitor::<T>::get();
 		if mint_amount.gt(&max_bnc_amount) {
@@ -262,32 +674,25 @@ impl<T: Config> MintTrait<T::AccountId, BalanceOf<T>, T::AssetId> for Module<T>
 		let bnc_amount = BncSum::<T>::get();
 		let total_score: BalanceOf<T> = VtokenWeightScore::<T>::iter()
 			.fold(zero_balance, |acc, x| acc.saturating_add(x.1.0).saturating_add(x.1.1));
+		ensure!(total_score.ne(&zero_balance), Error::<T>::AssetScoreNotExist);
 
-		// Traverse
+		// Feed each asset's own accumulator with its share of the pool instead of paying
+		// every minter directly; this bounds the loop by the number of registered assets
+		// rather than by the (unbounded) number of minters.
 		for (asset_id, (base_score, adjust_score)) in VtokenWeightScore::<T>::iter() {
-			let v_token_reward = base_score.saturating_add(adjust_score)
+			let asset_reward = base_score.saturating_add(adjust_score)
 				.saturating_mul(bnc_amount) / total_score;
-			// Get v_token point
-			let v_token_point: BalanceOf<T> = VtokenWeightMint::<T>::iter_prefix(&asset_id)
-				.fold(zero_balance, |acc, x| acc.saturating_add(x.1));
-			// Check asset point
-			if v_token_point.eq(&zero_balance) { continue }
-			// Traverse dispatch BNC reward
-			for (minter,point) in VtokenWeightMint::<T>::iter_prefix(asset_id) {
-				let minter_reward = point.saturating_mul(v_token_reward) / v_token_point;
-				if minter_reward.ne(&zero_balance) {
-					ensure!(
-						T::Currency::deposit_into_existing(&minter, minter_reward).is_ok(),
-						Error::<T>::DepositBncFailure
-					);
-				}
+			let total_point = TotalPointByAsset::<T>::get(&asset_id);
+			if total_point.ne(&zero_balance) {
+				let increment = Self::scaled_increment(asset_reward, total_point);
+				AccBncPerPointByAsset::<T>::mutate(&asset_id, |acc| {
+					*acc = acc.saturating_add(increment);
+				});
 			}
 		}
 
 		// Reset BncSum
 		BncSum::<T>::put(zero_balance);
-		// Clear BncMint
-		for _ in VtokenWeightMint::<T>::drain() {};
 		// Clear Monitor data
 		let zero_block_number: T::BlockNumber = Zero::zero();
 		BncMonitor::<T>::put(((zero_block_number, zero_balance), zero_balance, 0u32));
@@ -295,37 +700,42 @@ impl<T: Config> MintTrait<T::AccountId, BalanceOf<T>, T::AssetId> for Module<T>
 		Ok(())
 	}
 
+	// Locks `pledge_amount` for `MaxLockDuration` blocks (or extends an existing lock to at
+	// least that far out), contributing a weight that decays linearly to zero as the lock
+	// approaches its unlock block, vote-escrow style.
 	fn improve_v_token_weight(asset_id: T::AssetId, pledge_amount: BalanceOf<T>)
 		-> Result<(), Self::Error>
 	{
 		let base_amount = BalanceOf::<T>::from(T::PledgeBaseAmount::get());
 		ensure!(pledge_amount.gt(&base_amount), Error::<T>::PledgeAmountNotEnough);
-		// Add weight score
-		VtokenWeightScore::<T>::mutate(asset_id, |(_, v)| {
-			if let Some(x) = Fix::from_num::<u128>(pledge_amount.saturating_sub(base_amount)
-				.unique_saturated_into()).checked_int_log2()
-			{
-				*v = v.saturating_add(BalanceOf::<T>::from(x as u32));
-			}
-		});
+
+		let current_block = frame_system::Module::<T>::block_number();
+		let (existing_amount, existing_unlock) = VTokenLock::<T>::get(&asset_id);
+		let new_amount = existing_amount.saturating_add(pledge_amount);
+		let max_duration_unlock = current_block.saturating_add(T::BlockNumber::from(T::MaxLockDuration::get()));
+		let new_unlock = existing_unlock.max(max_duration_unlock);
+
+		Self::reschedule_lock(asset_id, existing_amount, existing_unlock, new_amount, new_unlock, current_block);
 
 		Ok(())
 	}
 
+	// Withdraws `pledge_amount` from an asset's vote-escrow lock; rejected until the lock's
+	// unlock block has been reached.
 	fn withdraw_v_token_pledge(asset_id: T::AssetId, pledge_amount: BalanceOf<T>)
 		-> Result<(), Self::Error>
 	{
 		let base_amount = BalanceOf::<T>::from(T::PledgeBaseAmount::get());
 		ensure!(pledge_amount.gt(&base_amount), Error::<T>::PledgeAmountNotEnough);
-		// Reduce weight score
-		VtokenWeightScore::<T>::mutate(asset_id, |(_, v)| {
-			if let Some(x) = Fix::from_num::<u128>(pledge_amount.saturating_sub(base_amount)
-				.unique_saturated_into()).checked_int_log2()
-			{
-				*v = v.saturating_sub(BalanceOf::<T>::from(x as u32));
-			}
-		});
+
+		let current_block = frame_system::Module::<T>::block_number();
+		let (existing_amount, unlock_block) = VTokenLock::<T>::get(&asset_id);
+		ensure!(existing_amount.ne(&Zero::zero()), Error::<T>::LockNotExist);
+		ensure!(current_block.ge(&unlock_block), Error::<T>::LockNotExpired);
+
+		let new_amount = existing_amount.saturating_sub(pledge_amount);
+		Self::reschedule_lock(asset_id, existing_amount, unlock_block, new_amount, unlock_block, current_block);
 
 		Ok(())
 	}
-}
\ No newline at end of file
+}
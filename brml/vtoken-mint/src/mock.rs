@@ -0,0 +1,142 @@
+// Copyright 2019-2020 Liebi Technologies.
+// This file is part of Bifrost.
+
+// Bifrost is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Bifrost is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Bifrost.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use std::cell::RefCell;
+
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use orml_traits::{parameter_type_with_key, DataProvider};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+
+use crate::{self as mint, Config};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		0
+	};
+}
+
+pub type AssetId = u32;
+pub type Balance = u128;
+
+impl orml_tokens::Config for Test {
+	type Event = ();
+	type Balance = Balance;
+	type Amount = i128;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+thread_local! {
+	static ORACLE_PRICE: RefCell<Option<Balance>> = RefCell::new(None);
+}
+
+/// A `DataProvider` whose reading is set directly by tests via [`set_oracle_price`], so that
+/// fresh-tick and staleness behaviour in `on_finalize` can be driven deterministically.
+pub struct TestPriceProvider;
+
+impl DataProvider<AssetId, Balance> for TestPriceProvider {
+	fn get(_asset_id: &AssetId) -> Option<Balance> {
+		ORACLE_PRICE.with(|price| *price.borrow())
+	}
+}
+
+/// Set the value `TestPriceProvider` returns, simulating the oracle reporting `price`, or
+/// `None` to simulate the feed having nothing to offer.
+pub fn set_oracle_price(price: Option<Balance>) {
+	ORACLE_PRICE.with(|p| *p.borrow_mut() = price);
+}
+
+parameter_types! {
+	pub const PriceHalfBlockInterval: u32 = 10;
+	pub const MaxIssueBlockInterval: u32 = 10;
+	pub const MaxTxAmount: u32 = 100;
+	pub const PledgeBaseAmount: u32 = 10;
+	pub const MaxLockDuration: u32 = 100;
+	pub const BncCurrencyId: AssetId = 0;
+	pub const PriceStalenessThreshold: u32 = 5;
+}
+
+impl Config for Test {
+	type Event = ();
+	type AssetId = AssetId;
+	type MultiCurrency = orml_tokens::Module<Test>;
+	type PriceHalfBlockInterval = PriceHalfBlockInterval;
+	type MaxIssueBlockInterval = MaxIssueBlockInterval;
+	type MaxTxAmount = MaxTxAmount;
+	type PledgeBaseAmount = PledgeBaseAmount;
+	type MaxLockDuration = MaxLockDuration;
+	type PriceProvider = TestPriceProvider;
+	type BncCurrencyId = BncCurrencyId;
+	type PriceStalenessThreshold = PriceStalenessThreshold;
+	type ControlOrigin = frame_system::EnsureRoot<u64>;
+}
+
+pub type Mint = mint::Module<Test>;
+pub type System = frame_system::Module<Test>;
+
+/// Build the mock's genesis storage with `number_price` as the starting `BncPrice`.
+pub fn new_test_ext(number_price: (u64, Balance)) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	mint::GenesisConfig::<Test> { number_price }.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
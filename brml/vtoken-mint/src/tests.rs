@@ -0,0 +1,196 @@
+// Copyright 2019-2020 Liebi Technologies.
+// This file is part of Bifrost.
+
+// Bifrost is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Bifrost is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Bifrost.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use frame_support::traits::OnFinalize;
+use node_primitives::MintTrait;
+
+use crate::{mock::*, BncPrice, LastOracleUpdate};
+
+type MintModule = crate::Module<Test>;
+
+#[test]
+fn scaled_increment_and_accrued_reward_round_trip() {
+	// 100 generated across 3 points does not divide evenly; the PRECISION scaling should
+	// keep the rounding error confined to the accumulator, not lost outright.
+	let increment = MintModule::scaled_increment(100, 3);
+	let accrued_per_point = MintModule::accrued_reward(1, increment);
+	let accrued_for_all_points = MintModule::accrued_reward(3, increment);
+
+	assert!(accrued_per_point <= accrued_for_all_points / 3 + 1);
+	// The accumulator should credit at most the amount generated, never more.
+	assert!(accrued_for_all_points <= 100);
+}
+
+#[test]
+fn count_bnc_does_not_double_issue_when_both_models_active() {
+	new_test_ext((0, 100)).execute_with(|| {
+		crate::TotalPoint::<Test>::put(10);
+		crate::VtokenWeightScore::<Test>::insert(1u32, (10u128, 0u128));
+		crate::TotalPointByAsset::<Test>::insert(1u32, 10u128);
+
+		MintModule::count_bnc(100);
+
+		let settlement_side = MintModule::accrued_reward(10, crate::AccBncPerPoint::<Test>::get());
+		let weight_side = crate::BncSum::<Test>::get();
+
+		// Together the two models should only ever have been credited the 100 that was
+		// actually generated this block, not 100 apiece.
+		assert_eq!(settlement_side + weight_side, 100);
+	});
+}
+
+#[test]
+fn count_bnc_feeds_settlement_model_in_full_when_weight_model_inactive() {
+	new_test_ext((0, 100)).execute_with(|| {
+		crate::TotalPoint::<Test>::put(10);
+
+		MintModule::count_bnc(100);
+
+		assert_eq!(crate::BncSum::<Test>::get(), 0);
+		assert_eq!(MintModule::accrued_reward(10, crate::AccBncPerPoint::<Test>::get()), 100);
+	});
+}
+
+#[test]
+fn count_bnc_does_not_route_to_a_weight_model_with_no_active_minters() {
+	new_test_ext((0, 100)).execute_with(|| {
+		// An asset can have a registered score with no minters ever having pledged
+		// against it; `issue_bnc_by_weight` would credit such an asset nothing and
+		// then reset `BncSum` to zero, so that share must not be routed there.
+		crate::TotalPoint::<Test>::put(10);
+		crate::VtokenWeightScore::<Test>::insert(1u32, (10u128, 0u128));
+
+		MintModule::count_bnc(100);
+
+		assert_eq!(crate::BncSum::<Test>::get(), 0);
+		assert_eq!(MintModule::accrued_reward(10, crate::AccBncPerPoint::<Test>::get()), 100);
+	});
+}
+
+#[test]
+fn checkpoint_ve_decays_bias_and_drops_slope_once_lock_matures() {
+	new_test_ext((0, 100)).execute_with(|| {
+		let asset_id = 1u32;
+		// A 100-unit lock maturing in 100 blocks, with MaxLockDuration = 100, starts at a
+		// slope of 1 per block and a bias equal to the full locked amount.
+		MintModule::reschedule_lock(asset_id, 0, 0, 100, 100, 0);
+		assert_eq!(crate::TotalBias::<Test>::get(asset_id), 100);
+		assert_eq!(crate::TotalSlope::<Test>::get(asset_id), 1);
+
+		MintModule::checkpoint_ve(asset_id, 40);
+		assert_eq!(crate::TotalBias::<Test>::get(asset_id), 60);
+		assert_eq!(crate::TotalSlope::<Test>::get(asset_id), 1);
+
+		// Once the unlock block is reached, the scheduled slope change should fire and
+		// zero out both the decay rate and the remaining bias.
+		MintModule::checkpoint_ve(asset_id, 100);
+		assert_eq!(crate::TotalBias::<Test>::get(asset_id), 0);
+		assert_eq!(crate::TotalSlope::<Test>::get(asset_id), 0);
+	});
+}
+
+#[test]
+fn checkpoint_ve_clamps_residual_bias_when_amount_is_not_a_multiple_of_max_lock_duration() {
+	new_test_ext((0, 100)).execute_with(|| {
+		let asset_id = 1u32;
+		// 150 / 100 truncates to a slope of 1, so decaying for the full 100 blocks only
+		// removes 100 of the original 150 bias; without an explicit clamp, 50 would be
+		// left stuck in TotalBias after the lock has actually matured.
+		MintModule::reschedule_lock(asset_id, 0, 0, 150, 100, 0);
+		assert_eq!(crate::TotalBias::<Test>::get(asset_id), 150);
+		assert_eq!(crate::TotalSlope::<Test>::get(asset_id), 1);
+
+		MintModule::checkpoint_ve(asset_id, 100);
+		assert_eq!(crate::TotalBias::<Test>::get(asset_id), 0);
+		assert_eq!(crate::TotalSlope::<Test>::get(asset_id), 0);
+	});
+}
+
+#[test]
+fn extend_lock_and_increase_amount_reject_non_control_origin() {
+	new_test_ext((0, 100)).execute_with(|| {
+		crate::VTokenLock::<Test>::insert(1u32, (100u128, 100u64));
+
+		assert!(MintModule::extend_lock(frame_system::RawOrigin::Signed(1).into(), 1u32, 200).is_err());
+		assert!(MintModule::increase_amount(frame_system::RawOrigin::Signed(1).into(), 1u32, 50).is_err());
+	});
+}
+
+#[test]
+fn on_finalize_only_treats_a_changed_oracle_reading_as_a_fresh_tick() {
+	new_test_ext((0, 100)).execute_with(|| {
+		set_oracle_price(Some(50));
+		MintModule::on_finalize(1);
+		assert_eq!(BncPrice::<Test>::get(), (1, 50));
+		assert_eq!(LastOracleUpdate::<Test>::get(), 1);
+
+		// The oracle keeps returning the very same cached reading; `on_finalize` must not
+		// treat that as a fresh tick, or staleness would never be reachable.
+		MintModule::on_finalize(2);
+		assert_eq!(BncPrice::<Test>::get(), (1, 50));
+		assert_eq!(LastOracleUpdate::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn on_finalize_falls_back_to_halving_once_the_oracle_goes_stale() {
+	new_test_ext((0, 100)).execute_with(|| {
+		set_oracle_price(Some(50));
+		MintModule::on_finalize(1);
+		assert_eq!(BncPrice::<Test>::get(), (1, 50));
+
+		// No fresh reading for longer than `PriceStalenessThreshold`, and `PriceHalfBlockInterval`
+		// blocks have passed since the price was last recorded: halving should kick in.
+		let halving_block = 1 + PriceHalfBlockInterval::get() as u64;
+		MintModule::on_finalize(halving_block);
+		assert_eq!(BncPrice::<Test>::get(), (halving_block, 25));
+	});
+}
+
+#[test]
+fn on_finalize_keeps_halving_even_after_the_exact_interval_block_was_missed() {
+	new_test_ext((0, 100)).execute_with(|| {
+		set_oracle_price(Some(50));
+		MintModule::on_finalize(1);
+		assert_eq!(BncPrice::<Test>::get(), (1, 50));
+
+		// Jump straight past the block where the elapsed gap exactly equals
+		// PriceHalfBlockInterval; an `eq` check would miss this and never halve again.
+		let past_the_exact_window = 1 + PriceHalfBlockInterval::get() as u64 + 5;
+		MintModule::on_finalize(past_the_exact_window);
+		assert_eq!(BncPrice::<Test>::get(), (past_the_exact_window, 25));
+	});
+}
+
+#[test]
+fn set_bnc_price_survives_the_next_on_finalize_when_oracle_is_unchanged() {
+	new_test_ext((0, 100)).execute_with(|| {
+		set_oracle_price(Some(50));
+		MintModule::on_finalize(1);
+		assert_eq!(BncPrice::<Test>::get(), (1, 50));
+
+		// Governance overrides the oracle-driven price...
+		MintModule::set_bnc_price(frame_system::RawOrigin::Root.into(), 999).unwrap();
+		assert_eq!(BncPrice::<Test>::get().1, 999);
+
+		// ...and the oracle, still reporting the same 50 it always has, must not clobber
+		// that override on the very next block.
+		MintModule::on_finalize(2);
+		assert_eq!(BncPrice::<Test>::get().1, 999);
+	});
+}